@@ -0,0 +1,161 @@
+// Copyright 2019 The xi-editor Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Letting an application override druid's automatic clipboard backend
+//! selection.
+//!
+//! This exists for environments our auto-detection can't see through:
+//! WSL (where the right tool is `win32yank`, not any of the Linux
+//! helpers), a detached tmux session, or simply forcing the OSC 52 path
+//! for a remote session. [`Application::set_clipboard_provider`] should be
+//! called before [`RunLoop::run`](crate::runloop::RunLoop::run); every
+//! subsequent clipboard read or write consults it first.
+
+use std::sync::{OnceLock, RwLock};
+
+use super::provider::{run_copy, run_paste, ClipboardProvider};
+use super::ClipboardType;
+
+/// A clipboard backend, either one of druid's built-ins or a fully custom
+/// command specification.
+#[derive(Debug, Clone)]
+pub enum ClipboardBackend {
+    /// Use the platform's normal, automatically-selected backend.
+    Native,
+    /// `wl-copy`/`wl-paste`.
+    Wayland,
+    /// `xclip`.
+    XClip,
+    /// `xsel`.
+    XSel,
+    /// `win32yank`, for reaching the Windows clipboard from WSL.
+    Win32Yank,
+    /// tmux's clipboard passthrough (`tmux load-buffer`/`save-buffer`).
+    Tmux,
+    /// The OSC 52 terminal escape sequence.
+    Osc52,
+    /// No clipboard access at all; every read returns `None` and every
+    /// write is silently dropped.
+    None,
+    /// A user-provided copy/paste command for each [`ClipboardType`].
+    Custom(CustomClipboardSpec),
+}
+
+/// The program and arguments to run for copying and pasting, for both the
+/// standard clipboard and the primary selection.
+#[derive(Debug, Clone)]
+pub struct CustomClipboardSpec {
+    pub standard: CommandSpec,
+    pub primary: CommandSpec,
+}
+
+/// The program and arguments used to copy to, or paste from, a single
+/// clipboard target.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub copy_program: String,
+    pub copy_args: Vec<String>,
+    pub paste_program: String,
+    pub paste_args: Vec<String>,
+}
+
+impl CustomClipboardSpec {
+    fn command_for(&self, clipboard_type: ClipboardType) -> &CommandSpec {
+        match clipboard_type {
+            ClipboardType::Standard => &self.standard,
+            ClipboardType::Selection => &self.primary,
+        }
+    }
+}
+
+impl ClipboardBackend {
+    /// Writes `text` to the clipboard using this backend, returning `false`
+    /// if the backend is [`Native`](ClipboardBackend::Native) and the
+    /// caller should fall back to the platform's own implementation.
+    pub(crate) fn set_clipboard_contents(&self, text: &str, clipboard_type: ClipboardType) -> bool {
+        match self {
+            ClipboardBackend::Native => return false,
+            ClipboardBackend::Wayland => copy_via(ClipboardProvider::Wayland, clipboard_type, text),
+            ClipboardBackend::XClip => copy_via(ClipboardProvider::XClip, clipboard_type, text),
+            ClipboardBackend::XSel => copy_via(ClipboardProvider::XSel, clipboard_type, text),
+            ClipboardBackend::Win32Yank => run_copy("win32yank.exe", &["-i"], text),
+            ClipboardBackend::Tmux => run_copy("tmux", &["load-buffer", "-"], text),
+            ClipboardBackend::Osc52 => super::osc52::set_clipboard_contents(text, clipboard_type),
+            ClipboardBackend::None => (),
+            ClipboardBackend::Custom(spec) => {
+                let cmd = spec.command_for(clipboard_type);
+                run_copy(&cmd.copy_program, &cmd.copy_args, text);
+            }
+        }
+        true
+    }
+
+    /// Reads the clipboard as a string using this backend. Returns `None`
+    /// both when the backend has no contents and when the backend is
+    /// [`Native`](ClipboardBackend::Native); callers distinguish the
+    /// latter with [`ClipboardBackend::is_native`].
+    pub(crate) fn string_value(&self, clipboard_type: ClipboardType) -> Option<String> {
+        match self {
+            ClipboardBackend::Native => None,
+            ClipboardBackend::Wayland => paste_via(ClipboardProvider::Wayland, clipboard_type),
+            ClipboardBackend::XClip => paste_via(ClipboardProvider::XClip, clipboard_type),
+            ClipboardBackend::XSel => paste_via(ClipboardProvider::XSel, clipboard_type),
+            ClipboardBackend::Win32Yank => run_paste("win32yank.exe", &["-o"]),
+            ClipboardBackend::Tmux => run_paste("tmux", &["save-buffer", "-"]),
+            ClipboardBackend::Osc52 => None, // OSC 52 is write-only.
+            ClipboardBackend::None => None,
+            ClipboardBackend::Custom(spec) => {
+                let cmd = spec.command_for(clipboard_type);
+                run_paste(&cmd.paste_program, &cmd.paste_args)
+            }
+        }
+    }
+
+    pub(crate) fn is_native(&self) -> bool {
+        matches!(self, ClipboardBackend::Native)
+    }
+}
+
+/// Copies `text` using `provider`'s command table, reusing the same
+/// program/argument lookup as the auto-detected [`ClipboardProvider`] path
+/// so the two don't drift out of sync.
+fn copy_via(provider: ClipboardProvider, clipboard_type: ClipboardType, text: &str) {
+    if let Some((program, args)) = provider.copy_command(clipboard_type) {
+        run_copy(program, args, text);
+    }
+}
+
+/// Pastes using `provider`'s command table; see [`copy_via`].
+fn paste_via(provider: ClipboardProvider, clipboard_type: ClipboardType) -> Option<String> {
+    let (program, args) = provider.paste_command(clipboard_type)?;
+    run_paste(program, args)
+}
+
+fn config() -> &'static RwLock<ClipboardBackend> {
+    static CONFIG: OnceLock<RwLock<ClipboardBackend>> = OnceLock::new();
+    CONFIG.get_or_init(|| RwLock::new(ClipboardBackend::Native))
+}
+
+/// Overrides druid's automatically-selected clipboard backend.
+///
+/// This should be called before [`RunLoop::run`](crate::runloop::RunLoop::run);
+/// every clipboard read and write after that point consults it.
+pub fn set_clipboard_provider(backend: ClipboardBackend) {
+    *config().write().unwrap() = backend;
+}
+
+/// Returns the currently configured clipboard backend override.
+pub fn clipboard_provider() -> ClipboardBackend {
+    config().read().unwrap().clone()
+}
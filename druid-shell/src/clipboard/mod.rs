@@ -15,8 +15,52 @@
 //! Interacting with the system pasteboard/clipboard.
 
 use std::fmt::Debug;
+use std::path::PathBuf;
+
+mod base64;
+pub mod backend;
+pub(crate) mod osc52;
+pub(crate) mod provider;
 
 pub use crate::platform::clipboard::{self as platform, ClipboardContents};
+pub use backend::{ClipboardBackend, CommandSpec, CustomClipboardSpec};
+
+/// Which system clipboard a read or write should target.
+///
+/// On X11 and Wayland these are two genuinely distinct targets: the
+/// `Standard` clipboard (cut/copy/paste) and the `Selection` clipboard
+/// (the current selection, pasted with a middle click). macOS and Windows
+/// have no equivalent of the selection clipboard, so platforms without one
+/// treat `Selection` the same as `Standard`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardType {
+    /// The general-purpose clipboard used for cut/copy/paste.
+    Standard,
+    /// The "primary selection", as found on X11 and Wayland.
+    Selection,
+}
+
+/// Options controlling how [`Application::set_clipboard_contents_with_options`]
+/// writes a [`ClipboardItem`] onto the system clipboard.
+///
+/// [`Application::set_clipboard_contents_with_options`]: crate::Application::set_clipboard_contents_with_options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetOptions {
+    /// Whether to clear the clipboard's existing contents before writing
+    /// the new ones.
+    ///
+    /// When `false`, the given formats are layered onto whatever is
+    /// already on the clipboard instead of replacing it, which is useful
+    /// for writing an HTML+text+image bundle incrementally from different
+    /// parts of an app. Defaults to `true`.
+    pub clear: bool,
+}
+
+impl Default for SetOptions {
+    fn default() -> Self {
+        SetOptions { clear: true }
+    }
+}
 
 /// An item to be put on the clipboard.
 ///
@@ -33,6 +77,21 @@ pub enum ClipboardFormat {
         data: Vec<u8>,
         info: Box<dyn ClipboardWrite>,
     },
+    /// A raster image, as row-major, top-down RGBA8 pixel data.
+    Image {
+        width: usize,
+        height: usize,
+        bytes: Vec<u8>,
+    },
+    /// Rich HTML, with a plain-text alternative for applications that
+    /// can't consume HTML off the clipboard.
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    /// A list of file paths, as copied from (or to) a file manager like
+    /// Explorer.
+    FileList(Vec<PathBuf>),
     #[doc(hidden)]
     /// Adding future items will not be a breaking change.
     __NotExhaustive,
@@ -67,14 +126,17 @@ impl ClipboardFormat {
         match self {
             ClipboardFormat::Text(_) => true,
             ClipboardFormat::Custom { info, .. } => info.write_options().is_some(),
+            //TODO: macOS/GTK support raster images too (NSPasteboardTypePNG,
+            // the GDK_SELECTION image targets); for now this only round-trips
+            // through CF_DIBV5 on Windows.
+            ClipboardFormat::Image { .. } => cfg!(target_os = "windows"),
+            ClipboardFormat::Html { .. } => cfg!(target_os = "windows"),
+            ClipboardFormat::FileList(_) => cfg!(target_os = "windows"),
             ClipboardFormat::__NotExhaustive => false,
         }
     }
 }
 
-//TODO: make custom formats work on windows, gtk.
-// https://docs.microsoft.com/en-us/windows/win32/dataxchg/clipboard-formats#registered-clipboard-formats
-
 /// A trait for types that can be written to the clipboard.
 pub trait ClipboardWrite {
     /// Returns, for a given platform, additional information for writing
@@ -135,6 +197,20 @@ impl ClipboardWrite for Pdf {
             data_type: platform::DataType::Data,
         })
     }
+
+    #[cfg(target_os = "windows")]
+    fn write_options(&self) -> Option<platform::WriteOpts> {
+        Some(platform::WriteOpts {
+            identifier: "com.adobe.pdf",
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn write_options(&self) -> Option<platform::WriteOpts> {
+        Some(platform::WriteOpts {
+            identifier: platform::Identifier("com.adobe.pdf"),
+        })
+    }
 }
 
 impl ClipboardRead for Pdf {
@@ -147,6 +223,20 @@ impl ClipboardRead for Pdf {
         })
     }
 
+    #[cfg(target_os = "windows")]
+    fn read_options(&self) -> Option<platform::ReadOpts> {
+        Some(platform::ReadOpts {
+            identifier: "com.adobe.pdf",
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_options(&self) -> Option<platform::ReadOpts> {
+        Some(platform::ReadOpts {
+            identifier: platform::Identifier("com.adobe.pdf"),
+        })
+    }
+
     fn parse(&self, data: Vec<u8>) -> Option<Self::Data> {
         Some(data)
     }
@@ -0,0 +1,50 @@
+// Copyright 2019 The xi-editor Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Writing to the system clipboard via the OSC 52 terminal escape sequence.
+//!
+//! A terminal emulator can own the system clipboard on behalf of the
+//! program running inside it, and will do so in response to this escape
+//! sequence even when that program has no display server of its own to
+//! talk to (a headless box, an SSH session, a terminal multiplexer). This
+//! gives `set_clipboard_contents` a working fallback in exactly those
+//! cases. Select it explicitly with
+//! [`ClipboardBackend::Osc52`](super::ClipboardBackend::Osc52) via
+//! [`Application::set_clipboard_provider`](crate::Application::set_clipboard_provider).
+
+use std::io::{self, Write};
+
+use super::{base64, ClipboardType};
+
+/// Writes `text` to the clipboard by emitting an OSC 52 escape sequence to
+/// stdout, in the form `ESC ] 52 ; <selector> ; <base64> BEL`, where
+/// `selector` is `c` for [`ClipboardType::Standard`] or `p` for
+/// [`ClipboardType::Selection`].
+pub(crate) fn set_clipboard_contents(text: &str, clipboard_type: ClipboardType) {
+    let selector = match clipboard_type {
+        ClipboardType::Standard => 'c',
+        ClipboardType::Selection => 'p',
+    };
+    let payload = base64::encode(text.as_bytes());
+    let sequence = format!("\x1b]52;{};{}\x07", selector, payload);
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    if let Err(e) = handle
+        .write_all(sequence.as_bytes())
+        .and_then(|_| handle.flush())
+    {
+        log::warn!("failed to write OSC 52 clipboard escape: {}", e);
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2019 The xi-editor Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A command-based clipboard backend, for platforms where there is no
+//! reliable native API (or where the native API is unavailable, such as a
+//! headless session).
+//!
+//! At startup we probe `$PATH` for a handful of well-known clipboard
+//! helpers and pick the first one that's present: `wl-copy`/`wl-paste`
+//! under Wayland, `xclip` or `xsel` under X11. Copying spawns the copy
+//! command and pipes the bytes to its stdin; pasting spawns the paste
+//! command and reads its stdout. If none of these helpers are installed,
+//! we fall back to a no-op provider rather than failing.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use super::ClipboardType;
+
+/// A clipboard helper program, selected at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClipboardProvider {
+    Wayland,
+    XClip,
+    XSel,
+    /// No known clipboard helper is available; reads and writes are no-ops.
+    NoOp,
+}
+
+impl ClipboardProvider {
+    /// Returns the clipboard helper to use, probing `$PATH` only on the
+    /// first call and caching the result for every call after that.
+    pub(crate) fn detect() -> Self {
+        static DETECTED: OnceLock<ClipboardProvider> = OnceLock::new();
+        *DETECTED.get_or_init(Self::probe)
+    }
+
+    /// Probes `$PATH` for a usable clipboard helper, preferring Wayland's
+    /// tools when running under a Wayland compositor.
+    fn probe() -> Self {
+        let has_wayland_session = std::env::var_os("WAYLAND_DISPLAY").is_some();
+        if has_wayland_session && exists_on_path("wl-copy") && exists_on_path("wl-paste") {
+            ClipboardProvider::Wayland
+        } else if exists_on_path("xclip") {
+            ClipboardProvider::XClip
+        } else if exists_on_path("xsel") {
+            ClipboardProvider::XSel
+        } else {
+            ClipboardProvider::NoOp
+        }
+    }
+
+    /// The program and arguments used to copy to this provider's clipboard
+    /// helper. Shared with [`backend`](super::backend) so the built-in
+    /// `ClipboardBackend` variants don't re-declare the same tables.
+    pub(crate) fn copy_command(
+        self,
+        clipboard_type: ClipboardType,
+    ) -> Option<(&'static str, &'static [&'static str])> {
+        use ClipboardType::*;
+        match (self, clipboard_type) {
+            (ClipboardProvider::Wayland, Standard) => Some(("wl-copy", &[])),
+            (ClipboardProvider::Wayland, Selection) => Some(("wl-copy", &["--primary"])),
+            (ClipboardProvider::XClip, Standard) => Some(("xclip", &["-selection", "clipboard"])),
+            (ClipboardProvider::XClip, Selection) => Some(("xclip", &["-selection", "primary"])),
+            (ClipboardProvider::XSel, Standard) => Some(("xsel", &["--clipboard", "--input"])),
+            (ClipboardProvider::XSel, Selection) => Some(("xsel", &["--primary", "--input"])),
+            (ClipboardProvider::NoOp, _) => None,
+        }
+    }
+
+    /// The program and arguments used to paste from this provider's
+    /// clipboard helper. Shared with [`backend`](super::backend) so the
+    /// built-in `ClipboardBackend` variants don't re-declare the same
+    /// tables.
+    pub(crate) fn paste_command(
+        self,
+        clipboard_type: ClipboardType,
+    ) -> Option<(&'static str, &'static [&'static str])> {
+        use ClipboardType::*;
+        match (self, clipboard_type) {
+            (ClipboardProvider::Wayland, Standard) => Some(("wl-paste", &["--no-newline"])),
+            (ClipboardProvider::Wayland, Selection) => {
+                Some(("wl-paste", &["--primary", "--no-newline"]))
+            }
+            (ClipboardProvider::XClip, Standard) => Some(("xclip", &["-selection", "clipboard", "-o"])),
+            (ClipboardProvider::XClip, Selection) => Some(("xclip", &["-selection", "primary", "-o"])),
+            (ClipboardProvider::XSel, Standard) => Some(("xsel", &["--clipboard", "--output"])),
+            (ClipboardProvider::XSel, Selection) => Some(("xsel", &["--primary", "--output"])),
+            (ClipboardProvider::NoOp, _) => None,
+        }
+    }
+
+    /// Writes `text` to the clipboard by piping it to the selected helper's stdin.
+    pub(crate) fn set_clipboard_contents(self, text: &str, clipboard_type: ClipboardType) {
+        if let Some((program, args)) = self.copy_command(clipboard_type) {
+            run_copy(program, args, text);
+        }
+    }
+
+    /// Reads the clipboard contents as a string, via the selected helper's stdout.
+    pub(crate) fn string_value(self, clipboard_type: ClipboardType) -> Option<String> {
+        let (program, args) = self.paste_command(clipboard_type)?;
+        run_paste(program, args)
+    }
+}
+
+fn exists_on_path(program: &str) -> bool {
+    let path = match std::env::var_os("PATH") {
+        Some(path) => path,
+        None => return false,
+    };
+    std::env::split_paths(&path).any(|dir| dir.join(program).is_file())
+}
+
+/// Spawns `program args...`, pipes `text` to its stdin, and waits for it to exit.
+///
+/// Shared by the auto-detected [`ClipboardProvider`]s above and by the
+/// user-configurable backends in [`backend`](super::backend).
+pub(crate) fn run_copy(program: &str, args: &[impl AsRef<std::ffi::OsStr>], text: &str) {
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            log::warn!("failed to spawn clipboard helper '{}': {}", program, e);
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(e) = stdin.write_all(text.as_bytes()) {
+            log::warn!("failed to write to '{}' stdin: {}", program, e);
+        }
+    }
+    if let Err(e) = child.wait() {
+        log::warn!("failed to wait on clipboard helper '{}': {}", program, e);
+    }
+}
+
+/// Spawns `program args...` and captures its stdout as the clipboard contents.
+pub(crate) fn run_paste(program: &str, args: &[impl AsRef<std::ffi::OsStr>]) -> Option<String> {
+    match Command::new(program).args(args).output() {
+        Ok(output) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        Ok(output) => {
+            log::warn!(
+                "clipboard helper '{}' exited with status {}",
+                program,
+                output.status
+            );
+            None
+        }
+        Err(e) => {
+            log::warn!("failed to spawn clipboard helper '{}': {}", program, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2019 The xi-editor Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! GTK implementation of features at the application scope.
+
+use crate::clipboard::{ClipboardItem, ClipboardType, SetOptions};
+
+pub struct Application;
+
+impl Application {
+    pub fn quit() {
+        gtk::main_quit();
+    }
+
+    pub fn set_clipboard_contents(item: ClipboardItem, clipboard_type: ClipboardType) {
+        super::clipboard::set_clipboard_contents(item, clipboard_type)
+    }
+
+    /// Sets the contents of the system clipboard, with control over
+    /// whether its existing contents are cleared first.
+    ///
+    /// GTK's clipboard API has no equivalent of appending formats, so
+    /// `options.clear` has no effect on this platform; see
+    /// [`set_clipboard_contents_with_options`](super::clipboard::set_clipboard_contents_with_options).
+    pub fn set_clipboard_contents_with_options(
+        item: ClipboardItem,
+        clipboard_type: ClipboardType,
+        options: SetOptions,
+    ) {
+        super::clipboard::set_clipboard_contents_with_options(item, clipboard_type, options)
+    }
+
+    /// Overrides druid's automatically-selected clipboard backend.
+    ///
+    /// Should be called before [`RunLoop::run`](crate::runloop::RunLoop::run).
+    pub fn set_clipboard_provider(backend: crate::clipboard::ClipboardBackend) {
+        crate::clipboard::backend::set_clipboard_provider(backend);
+    }
+
+    /// Returns the currently configured clipboard backend override.
+    pub fn clipboard_provider() -> crate::clipboard::ClipboardBackend {
+        crate::clipboard::backend::clipboard_provider()
+    }
+}
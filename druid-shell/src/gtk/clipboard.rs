@@ -0,0 +1,209 @@
+// Copyright 2019 The xi-editor Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Interactions with the system pasteboard on GTK (X11/Wayland via GTK).
+//!
+//! GTK's own clipboard API is only reachable when we're connected to a
+//! display server; on a headless box (no `DISPLAY`/`WAYLAND_DISPLAY` at
+//! all) `gdk::Display::default()` returns `None` and we have nothing to
+//! talk to natively. In that case we fall back to the command-based
+//! [`provider`](crate::clipboard::provider).
+
+use gtk::prelude::*;
+
+use crate::clipboard::backend;
+use crate::clipboard::provider::ClipboardProvider;
+use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardRead, ClipboardType, SetOptions};
+
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardContents;
+
+fn gdk_atom_for(clipboard_type: ClipboardType) -> gdk::Atom {
+    match clipboard_type {
+        ClipboardType::Standard => gdk::SELECTION_CLIPBOARD,
+        ClipboardType::Selection => gdk::SELECTION_PRIMARY,
+    }
+}
+
+fn gtk_clipboard_for(clipboard_type: ClipboardType) -> Option<gtk::Clipboard> {
+    gdk::Display::default()
+        .map(|display| gtk::Clipboard::for_display(&display, &gdk_atom_for(clipboard_type)))
+}
+
+/// A trait that represents the contents of the system clipboard.
+impl ClipboardContents {
+    /// Return the contents of the clipboard as a string, if possible.
+    ///
+    /// If an override backend has been set with
+    /// [`Application::set_clipboard_provider`](crate::Application::set_clipboard_provider),
+    /// it takes priority over both the native GTK clipboard and the
+    /// auto-detected [`ClipboardProvider`].
+    pub fn string_value(&self, clipboard_type: ClipboardType) -> Option<String> {
+        let configured = backend::clipboard_provider();
+        if !configured.is_native() {
+            return configured.string_value(clipboard_type);
+        }
+        match gtk_clipboard_for(clipboard_type) {
+            Some(clipboard) => clipboard.wait_for_text().map(|s| s.to_string()),
+            None => ClipboardProvider::detect().string_value(clipboard_type),
+        }
+    }
+
+    /// Attempts to retrieve the type of data described by the provided
+    /// [`ClipboardRead`].
+    ///
+    /// [`ClipboardRead`]: trait.ClipboardRead.html
+    pub fn custom_value<T: ClipboardRead>(
+        &self,
+        clipboard_type: ClipboardType,
+        reader: &T,
+    ) -> Option<T::Data> {
+        let opts = reader.read_options()?;
+        let clipboard = gtk_clipboard_for(clipboard_type)?;
+        let target = opts.identifier.to_gdk_atom();
+        let selection = clipboard.wait_for_contents(&target)?;
+        reader.parse(selection.get_data())
+    }
+}
+
+pub fn set_clipboard_contents(item: ClipboardItem, clipboard_type: ClipboardType) {
+    set_clipboard_contents_with_options(item, clipboard_type, SetOptions::default())
+}
+
+/// Sets the contents of the system clipboard.
+///
+/// GTK's clipboard API always replaces its contents atomically when a new
+/// owner takes over, with no equivalent of appending formats to whatever
+/// is already there, so `options.clear` has no effect here; it exists
+/// only to keep this signature consistent with the other platforms.
+pub fn set_clipboard_contents_with_options(
+    item: ClipboardItem,
+    clipboard_type: ClipboardType,
+    _options: SetOptions,
+) {
+    let configured = backend::clipboard_provider();
+    if !configured.is_native() {
+        if let Some(text) = item.iter_supported().find_map(|fmt| match fmt {
+            ClipboardFormat::Text(s) => Some(s.clone()),
+            _ => None,
+        }) {
+            configured.set_clipboard_contents(&text, clipboard_type);
+        }
+        return;
+    }
+
+    let clipboard = match gtk_clipboard_for(clipboard_type) {
+        Some(clipboard) => clipboard,
+        None => {
+            if let Some(text) = item.iter_supported().find_map(|fmt| match fmt {
+                ClipboardFormat::Text(s) => Some(s.clone()),
+                _ => None,
+            }) {
+                ClipboardProvider::detect().set_clipboard_contents(&text, clipboard_type);
+            }
+            return;
+        }
+    };
+
+    // A GTK clipboard "set" call atomically replaces every target the
+    // clipboard previously owned, so we have to declare every supported
+    // format in one `set_with_data` call rather than calling it once per
+    // format (which would just have each call clobber the last).
+    let mut entries = Vec::new();
+    let mut payloads = Vec::new();
+    for fmt in item.iter_supported() {
+        match fmt {
+            ClipboardFormat::Text(string) => {
+                let info = payloads.len() as u32;
+                entries.push(gtk::TargetEntry::new(
+                    "UTF8_STRING",
+                    gtk::TargetFlags::empty(),
+                    info,
+                ));
+                payloads.push(ClipboardPayload::Text(string.clone()));
+            }
+            ClipboardFormat::Custom { data, info } => {
+                let opts = info.write_options().unwrap();
+                let info = payloads.len() as u32;
+                entries.push(gtk::TargetEntry::new(
+                    opts.identifier.0,
+                    gtk::TargetFlags::empty(),
+                    info,
+                ));
+                payloads.push(ClipboardPayload::Custom {
+                    target: opts.identifier.to_gdk_atom(),
+                    data: data.clone(),
+                });
+            }
+            other => log::warn!("unhandled clipboard data {:?}", other),
+        }
+    }
+
+    if entries.is_empty() {
+        return;
+    }
+
+    let set = clipboard.set_with_data(&entries, move |_, selection_data, info| {
+        match &payloads[info as usize] {
+            ClipboardPayload::Text(text) => {
+                selection_data.set_text(text);
+            }
+            ClipboardPayload::Custom { target, data } => {
+                selection_data.set(target, 8, data);
+            }
+        }
+    });
+    if !set {
+        log::warn!("failed to put clipboard data on {} target(s)", entries.len());
+    }
+}
+
+/// The data backing one of the targets declared in a `set_with_data` call,
+/// looked up by the `info` id GTK passes back to the callback.
+enum ClipboardPayload {
+    Text(String),
+    Custom { target: gdk::Atom, data: Vec<u8> },
+}
+
+/// Platform-specific options returned by [`ClipboardRead::read_options`]
+///
+/// [`ClipboardRead::read_options`]: trait.ClipboardRead.html#tymethod.read_options
+pub struct ReadOpts {
+    pub identifier: Identifier,
+}
+
+/// Platform-specific options returned by [`ClipboardWrite::write_options`]
+///
+/// [`ClipboardWrite::write_options`]: trait.ClipboardWrite.html#tymethod.write_options
+#[derive(Debug)]
+pub struct WriteOpts {
+    pub identifier: Identifier,
+}
+
+/// A GTK clipboard target, identified by a string name that becomes a
+/// [`GdkAtom`](gdk::Atom) the first time it's interned.
+#[derive(Debug, Clone, Copy)]
+pub struct Identifier(pub &'static str);
+
+impl Identifier {
+    pub(crate) fn to_gdk_atom(&self) -> gdk::Atom {
+        gdk::Atom::intern(self.0)
+    }
+}
+
+impl std::fmt::Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
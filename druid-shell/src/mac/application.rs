@@ -18,7 +18,7 @@
 
 use super::util;
 use crate::clipboard::platform::{DataType, WriteOpts};
-use crate::clipboard::{ClipboardFormat, ClipboardItem};
+use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardType, SetOptions};
 use cocoa::appkit::{NSApp, NSPasteboardTypeString};
 use cocoa::base::{id, nil, BOOL, YES};
 use cocoa::foundation::{NSInteger, NSUInteger};
@@ -76,13 +76,52 @@ impl Application {
     }
 
     /// Sets the contents of the system clipboard.
-    pub fn set_clipboard_contents(item: ClipboardItem) {
+    ///
+    /// Equivalent to calling [`set_clipboard_contents_with_options`] with
+    /// the default [`SetOptions`], which clears the pasteboard's existing
+    /// contents first.
+    ///
+    /// [`set_clipboard_contents_with_options`]: Self::set_clipboard_contents_with_options
+    pub fn set_clipboard_contents(item: ClipboardItem, clipboard_type: ClipboardType) {
+        Self::set_clipboard_contents_with_options(item, clipboard_type, SetOptions::default())
+    }
+
+    /// Sets the contents of the system clipboard, with control over
+    /// whether its existing contents are cleared first.
+    ///
+    /// If an override backend has been set with
+    /// [`Application::set_clipboard_provider`], it is consulted for
+    /// [`ClipboardFormat::Text`] instead of the general pasteboard, and
+    /// `options.clear` has no effect. Otherwise, macOS has no primary
+    /// selection, so `clipboard_type` is ignored and the general
+    /// pasteboard is always used.
+    ///
+    /// With `options.clear` set to `false`, the given formats are added to
+    /// the pasteboard's existing types instead of replacing them.
+    pub fn set_clipboard_contents_with_options(
+        item: ClipboardItem,
+        clipboard_type: ClipboardType,
+        options: SetOptions,
+    ) {
+        let configured = crate::clipboard::backend::clipboard_provider();
+        if !configured.is_native() {
+            if let Some(text) = item.iter_supported().find_map(|fmt| match fmt {
+                ClipboardFormat::Text(s) => Some(s.clone()),
+                _ => None,
+            }) {
+                configured.set_clipboard_contents(&text, clipboard_type);
+            }
+            return;
+        }
         unsafe {
             let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
-            let _: NSInteger = msg_send![pasteboard, clearContents];
-
             let pb_types = item.make_types_array();
-            let _: NSInteger = msg_send![pasteboard, declareTypes: pb_types owner: nil];
+            if options.clear {
+                let _: NSInteger = msg_send![pasteboard, clearContents];
+                let _: NSInteger = msg_send![pasteboard, declareTypes: pb_types owner: nil];
+            } else {
+                let _: NSInteger = msg_send![pasteboard, addTypes: pb_types owner: nil];
+            }
 
             for fmt in item.iter_supported() {
                 match fmt {
@@ -119,6 +158,18 @@ impl Application {
             }
         }
     }
+
+    /// Overrides druid's automatically-selected clipboard backend.
+    ///
+    /// Should be called before [`RunLoop::run`](crate::runloop::RunLoop::run).
+    pub fn set_clipboard_provider(backend: crate::clipboard::ClipboardBackend) {
+        crate::clipboard::backend::set_clipboard_provider(backend);
+    }
+
+    /// Returns the currently configured clipboard backend override.
+    pub fn clipboard_provider() -> crate::clipboard::ClipboardBackend {
+        crate::clipboard::backend::clipboard_provider()
+    }
 }
 
 /// Creates the appropriate NSObject from the provided data, given these `WriteOpts`.
@@ -19,7 +19,8 @@ use cocoa::base::{id, nil, BOOL, YES};
 use cocoa::foundation::NSArray;
 
 use super::util;
-use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardRead};
+use crate::clipboard::backend;
+use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardRead, ClipboardType};
 
 #[derive(Debug, Clone, Default)]
 pub struct ClipboardContents;
@@ -27,7 +28,17 @@ pub struct ClipboardContents;
 /// A trait that represents the contents of the system clipboard.
 impl ClipboardContents {
     /// Return the contents of the clipboard as a string, if possible.
-    pub fn string_value(&self) -> Option<String> {
+    ///
+    /// If an override backend has been set with
+    /// [`Application::set_clipboard_provider`](crate::Application::set_clipboard_provider),
+    /// it is consulted instead of the general pasteboard. Otherwise,
+    /// macOS has no primary selection, so `clipboard_type` is ignored and
+    /// the general pasteboard is always used.
+    pub fn string_value(&self, clipboard_type: ClipboardType) -> Option<String> {
+        let configured = backend::clipboard_provider();
+        if !configured.is_native() {
+            return configured.string_value(clipboard_type);
+        }
         unsafe {
             let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
             let contents: id = msg_send![pasteboard, stringForType: NSPasteboardTypeString];
@@ -43,10 +54,17 @@ impl ClipboardContents {
     /// [`ClipboardRead`].
     ///
     /// [`ClipboardRead`]: trait.ClipboardRead.html
+    ///
+    /// macOS has no primary selection, so `clipboard_type` is ignored and
+    /// the general pasteboard is always used.
     //NOTE: semantically, this should probably be returning a Result<T> or an
     // Option<Result<T>>, because parsing can fail. It isn't clear that anything
     // is really possible in that scenario, though, and the API is worse.
-    pub fn custom_value<T: ClipboardRead>(&self, reader: &T) -> Option<T::Data> {
+    pub fn custom_value<T: ClipboardRead>(
+        &self,
+        _clipboard_type: ClipboardType,
+        reader: &T,
+    ) -> Option<T::Data> {
         let opts = reader.read_options()?;
         let pb_type = opts.identifier.to_nsstring();
         unsafe {
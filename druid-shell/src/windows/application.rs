@@ -14,7 +14,7 @@
 
 //! Windows implementation of features at the application scope.
 
-use crate::clipboard::ClipboardItem;
+use crate::clipboard::{ClipboardItem, ClipboardType, SetOptions};
 
 pub struct Application;
 
@@ -29,7 +29,32 @@ impl Application {
         super::clipboard::get_clipboard_contents()
     }
 
-    pub fn set_clipboard_contents(item: ClipboardItem) {
-        super::clipboard::set_clipboard_contents(item)
+    pub fn set_clipboard_contents(item: ClipboardItem, clipboard_type: ClipboardType) {
+        super::clipboard::set_clipboard_contents(item, clipboard_type)
+    }
+
+    /// Sets the contents of the system clipboard, with control over
+    /// whether its existing contents are cleared first.
+    ///
+    /// With `options.clear` set to `false`, the given formats are layered
+    /// onto whatever is already on the clipboard instead of replacing it.
+    pub fn set_clipboard_contents_with_options(
+        item: ClipboardItem,
+        clipboard_type: ClipboardType,
+        options: SetOptions,
+    ) {
+        super::clipboard::set_clipboard_contents_with_options(item, clipboard_type, options)
+    }
+
+    /// Overrides druid's automatically-selected clipboard backend.
+    ///
+    /// Should be called before [`RunLoop::run`](crate::runloop::RunLoop::run).
+    pub fn set_clipboard_provider(backend: crate::clipboard::ClipboardBackend) {
+        crate::clipboard::backend::set_clipboard_provider(backend);
+    }
+
+    /// Returns the currently configured clipboard backend override.
+    pub fn clipboard_provider() -> crate::clipboard::ClipboardBackend {
+        crate::clipboard::backend::clipboard_provider()
     }
 }
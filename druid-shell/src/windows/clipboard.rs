@@ -16,29 +16,86 @@
 
 use std::ffi::CString;
 use std::mem;
+use std::path::PathBuf;
 use std::ptr;
 
-use winapi::shared::minwindef::{FALSE, UINT};
+use winapi::shared::minwindef::{FALSE, TRUE, UINT};
 use winapi::shared::ntdef::{CHAR, LPWSTR, WCHAR};
+use winapi::shared::windef::{HDROP, POINT};
 use winapi::shared::winerror::ERROR_SUCCESS;
 use winapi::um::errhandlingapi::GetLastError;
+use winapi::um::shellapi::{DragQueryFileW, DROPFILES};
+use winapi::um::synchapi::Sleep;
+use winapi::um::wingdi::{BITMAPV5HEADER, BI_BITFIELDS, LCS_SRGB};
 use winapi::um::winbase::{GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE};
 use winapi::um::winuser::{
     CloseClipboard, EmptyClipboard, EnumClipboardFormats, GetClipboardData,
-    GetClipboardFormatNameA, IsClipboardFormatAvailable, OpenClipboard, RegisterClipboardFormatA,
-    SetClipboardData, CF_UNICODETEXT,
+    GetClipboardFormatNameA, GetClipboardSequenceNumber, IsClipboardFormatAvailable,
+    OpenClipboard, RegisterClipboardFormatA, SetClipboardData, CF_DIBV5, CF_HDROP, CF_UNICODETEXT,
 };
 
-use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardRead};
+use crate::clipboard::{ClipboardFormat, ClipboardItem, ClipboardRead, ClipboardType, SetOptions};
 use crate::util::{FromWide, ToWide};
 
+/// The clipboard is a single global resource, and Windows hands it out
+/// exclusively: `OpenClipboard` fails outright if another process (or
+/// another window in our own process) is holding it, which routinely
+/// happens for a few milliseconds around a paste or a copy elsewhere on
+/// the system. `ScopedClipboard` retries the open a handful of times with
+/// a short sleep between attempts, then closes the clipboard for us on
+/// drop so every caller gets the same contention handling instead of
+/// bailing on the first failed open.
+struct ScopedClipboard;
+
+impl ScopedClipboard {
+    const MAX_ATTEMPTS: u32 = 5;
+    const RETRY_DELAY_MS: u32 = 10;
+
+    fn open() -> Option<ScopedClipboard> {
+        for attempt in 0..Self::MAX_ATTEMPTS {
+            unsafe {
+                if OpenClipboard(ptr::null_mut()) != FALSE {
+                    return Some(ScopedClipboard);
+                }
+            }
+            if attempt + 1 < Self::MAX_ATTEMPTS {
+                unsafe { Sleep(Self::RETRY_DELAY_MS) };
+            }
+        }
+        log::warn!(
+            "failed to open clipboard after {} attempts; error {}",
+            Self::MAX_ATTEMPTS,
+            unsafe { GetLastError() }
+        );
+        None
+    }
+}
+
+impl Drop for ScopedClipboard {
+    fn drop(&mut self) {
+        unsafe {
+            CloseClipboard();
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ClipboardContents;
 
 /// A trait that represents the contents of the system clipboard.
 impl ClipboardContents {
     /// Return the contents of the clipboard as a string, if possible.
-    pub fn string_value(&self) -> Option<String> {
+    ///
+    /// If an override backend has been set with
+    /// [`Application::set_clipboard_provider`](crate::Application::set_clipboard_provider),
+    /// it is consulted instead. Otherwise, Windows has no primary
+    /// selection, so `clipboard_type` is ignored and the single system
+    /// clipboard is always used.
+    pub fn string_value(&self, clipboard_type: ClipboardType) -> Option<String> {
+        let configured = crate::clipboard::backend::clipboard_provider();
+        if !configured.is_native() {
+            return configured.string_value(clipboard_type);
+        }
         None
     }
 
@@ -46,17 +103,21 @@ impl ClipboardContents {
     /// [`ClipboardRead`].
     ///
     /// [`ClipboardRead`]: trait.ClipboardRead.html
+    ///
+    /// Windows has no primary selection, so `clipboard_type` is ignored
+    /// and the single system clipboard is always used.
     //NOTE: semantically, this should probably be returning a Result<T> or an
     // Option<Result<T>>, because parsing can fail. It isn't clear that anything
     // is really possible in that scenario, though, and the API is worse.
-    pub fn custom_value<T: ClipboardRead>(&self, reader: &T) -> Option<T::Data> {
+    pub fn custom_value<T: ClipboardRead>(
+        &self,
+        _clipboard_type: ClipboardType,
+        reader: &T,
+    ) -> Option<T::Data> {
         let opts = reader.read_options()?;
         let format = register_identifier(opts.identifier)?;
+        let _clipboard = ScopedClipboard::open()?;
         unsafe {
-            if OpenClipboard(ptr::null_mut()) == FALSE {
-                return None;
-            }
-
             if IsClipboardFormatAvailable(format) != 0 {
                 let handle = GetClipboardData(format);
                 let size = GlobalSize(handle);
@@ -65,7 +126,6 @@ impl ClipboardContents {
                 ptr::copy_nonoverlapping(locked, dest.as_mut_ptr(), size);
                 dest.set_len(size);
                 GlobalUnlock(handle);
-                CloseClipboard();
                 return reader.parse(dest);
             }
 
@@ -76,10 +136,89 @@ impl ClipboardContents {
                     get_format_name(format)
                 );
             }
-            CloseClipboard();
         }
         None
     }
+
+    /// Returns the clipboard contents as RGBA8 pixel data, if a `CF_DIBV5`
+    /// bitmap is present.
+    ///
+    /// Windows has no primary selection, so `clipboard_type` is ignored
+    /// and the single system clipboard is always used.
+    pub fn image_value(&self, _clipboard_type: ClipboardType) -> Option<(usize, usize, Vec<u8>)> {
+        let _clipboard = ScopedClipboard::open()?;
+        unsafe {
+            if IsClipboardFormatAvailable(CF_DIBV5) != 0 {
+                let handle = GetClipboardData(CF_DIBV5);
+                let size = GlobalSize(handle);
+                let locked = GlobalLock(handle) as *const u8;
+                let data = std::slice::from_raw_parts(locked, size);
+                let result = decode_dibv5(data);
+                GlobalUnlock(handle);
+                result
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the inner HTML fragment from the clipboard's "HTML Format",
+    /// if present.
+    ///
+    /// Windows has no primary selection, so `clipboard_type` is ignored
+    /// and the single system clipboard is always used.
+    pub fn html_value(&self, _clipboard_type: ClipboardType) -> Option<String> {
+        let format = register_identifier("HTML Format")?;
+        let _clipboard = ScopedClipboard::open()?;
+        unsafe {
+            if IsClipboardFormatAvailable(format) != 0 {
+                let handle = GetClipboardData(format);
+                let size = GlobalSize(handle);
+                let locked = GlobalLock(handle) as *const u8;
+                let data = std::slice::from_raw_parts(locked, size);
+                let result = decode_cf_html(data);
+                GlobalUnlock(handle);
+                result
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the list of file paths on the clipboard, if a `CF_HDROP` is
+    /// present (as placed there by Explorer's copy/cut).
+    ///
+    /// Windows has no primary selection, so `clipboard_type` is ignored
+    /// and the single system clipboard is always used.
+    pub fn file_list_value(&self, _clipboard_type: ClipboardType) -> Option<Vec<PathBuf>> {
+        let _clipboard = ScopedClipboard::open()?;
+        unsafe {
+            if IsClipboardFormatAvailable(CF_HDROP) != 0 {
+                let hdrop = GetClipboardData(CF_HDROP) as HDROP;
+                Some(read_hdrop(hdrop))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the clipboard's current sequence number.
+    ///
+    /// Windows bumps this every time the clipboard's contents change, and
+    /// reading it doesn't require opening the clipboard, so it's a cheap
+    /// way to poll for changes without re-reading (and re-parsing) the
+    /// whole payload. Cache the value you get back and compare against it
+    /// later with [`has_changed_since`](Self::has_changed_since).
+    pub fn sequence_number(&self) -> u32 {
+        unsafe { GetClipboardSequenceNumber() }
+    }
+
+    /// Returns `true` if the clipboard has changed since it had sequence
+    /// number `seq`, as previously returned by
+    /// [`sequence_number`](Self::sequence_number).
+    pub fn has_changed_since(&self, seq: u32) -> bool {
+        self.sequence_number() != seq
+    }
 }
 
 /// Platform-specific options returned by [`ClipboardRead::read_options`]
@@ -98,12 +237,47 @@ pub struct WriteOpts {
     pub identifier: &'static str,
 }
 
-pub fn set_clipboard_contents(new_contents: ClipboardItem) {
+/// Sets the contents of the system clipboard.
+///
+/// Equivalent to calling [`set_clipboard_contents_with_options`] with the
+/// default [`SetOptions`], which clears the clipboard's existing contents
+/// first.
+pub fn set_clipboard_contents(new_contents: ClipboardItem, clipboard_type: ClipboardType) {
+    set_clipboard_contents_with_options(new_contents, clipboard_type, SetOptions::default())
+}
+
+/// Sets the contents of the system clipboard, with control over whether
+/// its existing contents are cleared first.
+///
+/// If an override backend has been set with
+/// [`Application::set_clipboard_provider`](crate::Application::set_clipboard_provider),
+/// it is consulted for [`ClipboardFormat::Text`] instead of the system
+/// clipboard, and `options.clear` has no effect. Otherwise, Windows has no
+/// primary selection, so `clipboard_type` is ignored and the single
+/// system clipboard is always used.
+pub fn set_clipboard_contents_with_options(
+    new_contents: ClipboardItem,
+    clipboard_type: ClipboardType,
+    options: SetOptions,
+) {
+    let configured = crate::clipboard::backend::clipboard_provider();
+    if !configured.is_native() {
+        if let Some(text) = new_contents.iter_supported().find_map(|fmt| match fmt {
+            ClipboardFormat::Text(s) => Some(s.clone()),
+            _ => None,
+        }) {
+            configured.set_clipboard_contents(&text, clipboard_type);
+        }
+        return;
+    }
+    let _clipboard = match ScopedClipboard::open() {
+        Some(clipboard) => clipboard,
+        None => return,
+    };
     unsafe {
-        if OpenClipboard(ptr::null_mut()) == FALSE {
-            return;
+        if options.clear {
+            EmptyClipboard();
         }
-        EmptyClipboard();
 
         for fmt in new_contents.iter_supported() {
             match fmt {
@@ -135,24 +309,247 @@ pub fn set_clipboard_contents(new_contents: ClipboardItem) {
                         log::warn!("failed to set clipboard {}", GetLastError());
                     }
                 }
+                ClipboardFormat::Image {
+                    width,
+                    height,
+                    bytes,
+                } => {
+                    let dib = match encode_dibv5(*width, *height, bytes) {
+                        Some(dib) => dib,
+                        None => {
+                            log::warn!(
+                                "invalid image dimensions/data for clipboard ({}x{}, {} bytes)",
+                                width,
+                                height,
+                                bytes.len()
+                            );
+                            continue;
+                        }
+                    };
+                    let dib_handle = GlobalAlloc(GMEM_MOVEABLE, dib.len());
+                    let locked = GlobalLock(dib_handle) as *mut u8;
+                    ptr::copy_nonoverlapping(dib.as_ptr(), locked, dib.len());
+                    GlobalUnlock(dib_handle);
+                    let result = SetClipboardData(CF_DIBV5, dib_handle);
+                    if result.is_null() {
+                        log::warn!("failed to set clipboard {}", GetLastError());
+                    }
+                }
+                ClipboardFormat::Html { html, alt_text } => {
+                    if let Some(format) = register_identifier("HTML Format") {
+                        let buf = encode_cf_html(html);
+                        let handle = GlobalAlloc(GMEM_MOVEABLE, buf.len());
+                        let locked = GlobalLock(handle) as *mut u8;
+                        ptr::copy_nonoverlapping(buf.as_ptr(), locked, buf.len());
+                        GlobalUnlock(handle);
+                        if SetClipboardData(format, handle).is_null() {
+                            log::warn!("failed to set clipboard {}", GetLastError());
+                        }
+                    }
+                    if let Some(alt_text) = alt_text {
+                        let wstr = alt_text.to_wide();
+                        let wstr_copy =
+                            GlobalAlloc(GMEM_MOVEABLE, wstr.len() * mem::size_of::<WCHAR>());
+                        let locked = GlobalLock(wstr_copy) as LPWSTR;
+                        ptr::copy_nonoverlapping(wstr.as_ptr(), locked, wstr.len());
+                        GlobalUnlock(wstr_copy);
+                        if SetClipboardData(CF_UNICODETEXT, wstr_copy).is_null() {
+                            log::warn!("failed to set clipboard {}", GetLastError());
+                        }
+                    }
+                }
+                ClipboardFormat::FileList(paths) => {
+                    let buf = encode_hdrop(paths);
+                    let handle = GlobalAlloc(GMEM_MOVEABLE, buf.len());
+                    let locked = GlobalLock(handle) as *mut u8;
+                    ptr::copy_nonoverlapping(buf.as_ptr(), locked, buf.len());
+                    GlobalUnlock(handle);
+                    let result = SetClipboardData(CF_HDROP, handle);
+                    if result.is_null() {
+                        log::warn!("failed to set clipboard {}", GetLastError());
+                    }
+                }
                 other => log::warn!("unhandled clipboard data {:?}", other),
             }
         }
-        CloseClipboard();
     }
 }
 
 /// old impl, will be deleted soon
 pub(crate) fn get_clipboard_contents() -> Option<ClipboardItem> {
+    let _clipboard = ScopedClipboard::open()?;
+    unsafe { get_clipboard_impl() }
+}
+
+/// Builds a `CF_DIBV5`-compatible buffer (a `BITMAPV5HEADER` followed by
+/// bottom-up BGRA rows) from top-down RGBA8 pixel data.
+fn encode_dibv5(width: usize, height: usize, rgba: &[u8]) -> Option<Vec<u8>> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let byte_len = width.checked_mul(height)?.checked_mul(4)?;
+    if rgba.len() != byte_len {
+        return None;
+    }
+
+    let header_size = mem::size_of::<BITMAPV5HEADER>();
+    let mut header: BITMAPV5HEADER = unsafe { mem::zeroed() };
+    header.bV5Size = header_size as u32;
+    header.bV5Width = width as i32;
+    header.bV5Height = height as i32;
+    header.bV5Planes = 1;
+    header.bV5BitCount = 32;
+    header.bV5Compression = BI_BITFIELDS;
+    header.bV5RedMask = 0x00FF0000;
+    header.bV5GreenMask = 0x0000FF00;
+    header.bV5BlueMask = 0x000000FF;
+    header.bV5AlphaMask = 0xFF000000;
+    header.bV5CSType = LCS_SRGB as u32;
+
+    let mut buf = Vec::with_capacity(header_size + rgba.len());
     unsafe {
-        if OpenClipboard(ptr::null_mut()) == FALSE {
-            return None;
+        let header_bytes =
+            std::slice::from_raw_parts(&header as *const _ as *const u8, header_size);
+        buf.extend_from_slice(header_bytes);
+    }
+
+    // DIB rows are stored bottom-up, and Windows expects BGRA, not RGBA.
+    for row in rgba.chunks(width * 4).rev() {
+        for pixel in row.chunks(4) {
+            buf.push(pixel[2]);
+            buf.push(pixel[1]);
+            buf.push(pixel[0]);
+            buf.push(pixel[3]);
+        }
+    }
+    Some(buf)
+}
+
+/// Parses a `CF_DIBV5` buffer (a `BITMAPV5HEADER` followed by bottom-up
+/// BGRA rows) back into top-down RGBA8 pixel data.
+fn decode_dibv5(data: &[u8]) -> Option<(usize, usize, Vec<u8>)> {
+    let header_size = mem::size_of::<BITMAPV5HEADER>();
+    if data.len() < header_size {
+        return None;
+    }
+    let header = unsafe { &*(data.as_ptr() as *const BITMAPV5HEADER) };
+    // The clipboard contents are whatever another process put there, so we
+    // can't trust the header to describe the 32bpp BGRA bitmap we know how
+    // to decode, or even to contain sane dimensions.
+    if header.bV5Width <= 0 || header.bV5Height == 0 {
+        return None;
+    }
+    if header.bV5BitCount != 32 || header.bV5Compression != BI_BITFIELDS {
+        return None;
+    }
+    let width = header.bV5Width as usize;
+    let height = header.bV5Height.unsigned_abs() as usize;
+    let bottom_up = header.bV5Height > 0;
+    let byte_len = width.checked_mul(height)?.checked_mul(4)?;
+
+    let pixels = &data[header_size..];
+    if pixels.len() < byte_len {
+        return None;
+    }
+
+    let mut rgba = vec![0u8; byte_len];
+    for (row_idx, row) in pixels.chunks(width * 4).take(height).enumerate() {
+        let dest_row = if bottom_up { height - 1 - row_idx } else { row_idx };
+        let dest = &mut rgba[dest_row * width * 4..(dest_row + 1) * width * 4];
+        for (src_pixel, dest_pixel) in row.chunks(4).zip(dest.chunks_mut(4)) {
+            dest_pixel[0] = src_pixel[2];
+            dest_pixel[1] = src_pixel[1];
+            dest_pixel[2] = src_pixel[0];
+            dest_pixel[3] = src_pixel[3];
         }
+    }
+    Some((width, height, rgba))
+}
 
-        let result = get_clipboard_impl();
-        CloseClipboard();
-        result
+/// Builds a buffer in the "HTML Format" layout: a textual header giving
+/// byte offsets into this same buffer, followed by the HTML wrapped in
+/// `<!--StartFragment-->`/`<!--EndFragment-->` markers.
+///
+/// The offset fields are zero-padded to a fixed width, so the header's
+/// length doesn't depend on the offset values it contains — we can compute
+/// the real offsets directly instead of writing placeholders and patching
+/// them in afterward.
+fn encode_cf_html(html: &str) -> Vec<u8> {
+    const START_MARKER: &str = "<!--StartFragment-->";
+    const END_MARKER: &str = "<!--EndFragment-->";
+
+    let header_len = cf_html_header(0, 0, 0, 0).len();
+    let start_html = header_len;
+    let start_fragment = start_html + START_MARKER.len();
+    let end_fragment = start_fragment + html.len();
+    let end_html = end_fragment + END_MARKER.len();
+
+    let mut buf = cf_html_header(start_html, end_html, start_fragment, end_fragment).into_bytes();
+    buf.extend_from_slice(START_MARKER.as_bytes());
+    buf.extend_from_slice(html.as_bytes());
+    buf.extend_from_slice(END_MARKER.as_bytes());
+    buf
+}
+
+fn cf_html_header(start_html: usize, end_html: usize, start_fragment: usize, end_fragment: usize) -> String {
+    format!(
+        "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n",
+        start_html, end_html, start_fragment, end_fragment
+    )
+}
+
+/// Recovers the inner HTML from a buffer in the "HTML Format" layout, by
+/// slicing out whatever sits between the fragment markers.
+fn decode_cf_html(data: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(data);
+    const START_MARKER: &str = "<!--StartFragment-->";
+    const END_MARKER: &str = "<!--EndFragment-->";
+    let start = text.find(START_MARKER)? + START_MARKER.len();
+    let end = start + text[start..].find(END_MARKER)?;
+    Some(text[start..end].to_string())
+}
+
+/// Reads every file path out of a `CF_HDROP` handle.
+unsafe fn read_hdrop(hdrop: HDROP) -> Vec<PathBuf> {
+    let count = DragQueryFileW(hdrop, 0xFFFF_FFFF, ptr::null_mut(), 0);
+    let mut paths = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let len = DragQueryFileW(hdrop, i, ptr::null_mut(), 0);
+        let mut buf: Vec<u16> = vec![0; len as usize + 1];
+        DragQueryFileW(hdrop, i, buf.as_mut_ptr(), len + 1);
+        if let Some(path) = (buf.as_ptr() as LPWSTR).from_wide() {
+            paths.push(PathBuf::from(path));
+        }
+    }
+    paths
+}
+
+/// Builds a `DROPFILES` structure followed by the double-null-terminated,
+/// wide-encoded concatenation of `paths`, suitable for `SetClipboardData(CF_HDROP, ..)`.
+fn encode_hdrop(paths: &[PathBuf]) -> Vec<u8> {
+    let header_size = mem::size_of::<DROPFILES>();
+    let mut wide_paths = Vec::new();
+    for path in paths {
+        wide_paths.extend(path.to_string_lossy().to_wide());
+    }
+    wide_paths.push(0); // second terminating null, ending the whole list.
+
+    let mut header: DROPFILES = unsafe { mem::zeroed() };
+    header.pFiles = header_size as u32;
+    header.pt = POINT { x: 0, y: 0 };
+    header.fNC = FALSE;
+    header.fWide = TRUE;
+
+    let mut buf = Vec::with_capacity(header_size + wide_paths.len() * mem::size_of::<u16>());
+    unsafe {
+        let header_bytes =
+            std::slice::from_raw_parts(&header as *const _ as *const u8, header_size);
+        buf.extend_from_slice(header_bytes);
+    }
+    for unit in &wide_paths {
+        buf.extend_from_slice(&unit.to_le_bytes());
     }
+    buf
 }
 
 fn register_identifier(ident: &str) -> Option<UINT> {